@@ -1,9 +1,10 @@
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::http::Method;
+use crate::http::{Method, Url};
 use crate::middleware::{Middleware, Next};
-use crate::{HttpClient, Request, RequestBuilder, Response, Result};
+use crate::{Config, HttpClient, Request, RequestBuilder, Response, Result};
 
 use futures_util::future::BoxFuture;
 
@@ -28,10 +29,48 @@ use http_client::h1::H1Client;
 /// ```
 pub struct Client {
     http_client: Arc<dyn HttpClient>,
+    /// Holds the configuration shared by every request this client sends.
+    config: Arc<Config>,
     /// Holds the middleware stack.
     middleware: Arc<Vec<Arc<dyn Middleware>>>,
 }
 
+/// A per-request timeout override, stored in a `Request`'s extensions by
+/// [`RequestBuilder::timeout`] and read by [`Client::send`].
+///
+/// An inner value of `Some(duration)` races the request against a timer of
+/// that length, while `None` explicitly disables the client's default timeout
+/// for this one request.
+///
+/// [`RequestBuilder::timeout`]: crate::RequestBuilder::timeout
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RequestTimeout(pub(crate) Option<Duration>);
+
+impl RequestBuilder {
+    /// Set a timeout for this single request, overriding the client-wide
+    /// default configured on [`Config`](crate::Config).
+    ///
+    /// `Some(duration)` races the request against a timer of that length;
+    /// `None` disables the client default for this request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[async_std::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    /// use std::time::Duration;
+    ///
+    /// let res = surf::get("https://httpbin.org/get")
+    ///     .timeout(Some(Duration::from_secs(5)))
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.req.set_ext(RequestTimeout(timeout));
+        self
+    }
+}
+
 impl Clone for Client {
     /// Clones the Client.
     ///
@@ -42,6 +81,7 @@ impl Clone for Client {
     fn clone(&self) -> Self {
         Self {
             http_client: self.http_client.clone(),
+            config: self.config.clone(),
             middleware: Arc::new(self.middleware.iter().cloned().collect()),
         }
     }
@@ -85,10 +125,57 @@ impl Client {
     pub fn with_http_client(http_client: Arc<dyn HttpClient>) -> Self {
         Self {
             http_client,
+            config: Arc::new(Config::new()),
+            middleware: Arc::new(vec![
+                #[cfg(feature = "middleware-logger")]
+                Arc::new(crate::middleware::Logger::new()),
+            ]),
+        }
+    }
+
+    /// Create a new instance from a [`Config`].
+    ///
+    /// The `Config`'s backend is used when one was supplied, otherwise the
+    /// default client for the enabled backend feature is constructed. When a
+    /// TLS configuration is set it is applied to the `h1-client` backend via
+    /// its `http_client::Config`.
+    pub(crate) fn from_config(mut config: Config) -> Result<Self> {
+        let http_client = match config.http_client.take() {
+            Some(http_client) => http_client,
+            None => {
+                #[cfg(all(feature = "native-client", not(feature = "h1-client")))]
+                let client = NativeClient::new();
+                #[cfg(feature = "h1-client")]
+                let client = {
+                    let mut client = H1Client::new();
+                    if let Some(tls_config) = config.tls_config.clone() {
+                        let mut backend_config = http_client::Config::default();
+                        backend_config.tls_config = Some(tls_config);
+                        client.set_config(backend_config)?;
+                    }
+                    client
+                };
+                Arc::new(client)
+            }
+        };
+        Ok(Self {
+            http_client,
+            config: Arc::new(config),
             middleware: Arc::new(vec![
                 #[cfg(feature = "middleware-logger")]
                 Arc::new(crate::middleware::Logger::new()),
             ]),
+        })
+    }
+
+    /// Resolve a request URI against the configured base URL.
+    ///
+    /// When a base URL is set, `uri` is treated as relative and joined onto it
+    /// via [`Url::join`]; otherwise `uri` is parsed as an absolute URL.
+    fn resolve_url(&self, uri: impl AsRef<str>) -> Url {
+        match &self.config.base_url {
+            Some(base) => base.join(uri.as_ref()).unwrap(),
+            None => uri.as_ref().parse().unwrap(),
         }
     }
 
@@ -129,9 +216,26 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn send(&self, req: impl Into<Request>) -> BoxFuture<'static, Result<Response>> {
-        let req: Request = req.into();
+        let mut req: Request = req.into();
         let http_client = self.http_client.clone();
         let middleware = self.middleware.clone();
+        let config = self.config.clone();
+
+        // Merge the client's default headers into the request without
+        // overwriting any the caller already set.
+        for (name, value) in config.headers.iter() {
+            if req.header(name).is_none() {
+                req.insert_header(name.clone(), value.clone());
+            }
+        }
+
+        // A per-request timeout overrides the client-wide default; an explicit
+        // per-request `None` disables the timeout for this request.
+        let timeout = match req.ext::<RequestTimeout>() {
+            Some(t) => t.0,
+            None => config.timeout,
+        };
+
         Box::pin(async move {
             let next = Next::new(&middleware, &|req, client| {
                 Box::pin(async move {
@@ -140,7 +244,16 @@ impl Client {
                 })
             });
 
-            let res = next.run(req, Client::with_http_client(http_client)).await?;
+            let run = next.run(req, Client::with_http_client(http_client));
+            let res = match timeout {
+                Some(dur) => async_std::future::timeout(dur, run).await.map_err(|_| {
+                    http_types::Error::from_str(
+                        http_types::StatusCode::RequestTimeout,
+                        "request timed out",
+                    )
+                })??,
+                None => run.await?,
+            };
             Ok(Response::new(res.into()))
         })
     }
@@ -258,7 +371,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn get(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Get, uri).with_client(self.clone())
     }
 
@@ -282,7 +395,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn head(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Head, uri).with_client(self.clone())
     }
 
@@ -306,7 +419,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn post(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Post, uri).with_client(self.clone())
     }
 
@@ -330,7 +443,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn put(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Put, uri).with_client(self.clone())
     }
 
@@ -354,7 +467,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn delete(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Delete, uri).with_client(self.clone())
     }
 
@@ -378,7 +491,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn connect(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Connect, uri).with_client(self.clone())
     }
 
@@ -402,7 +515,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn options(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Options, uri).with_client(self.clone())
     }
 
@@ -426,7 +539,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn trace(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Trace, uri).with_client(self.clone())
     }
 
@@ -450,7 +563,7 @@ impl Client {
     /// # Ok(()) }
     /// ```
     pub fn patch(&self, uri: impl AsRef<str>) -> RequestBuilder {
-        let uri = uri.as_ref().parse().unwrap();
+        let uri = self.resolve_url(uri);
         RequestBuilder::new(Method::Patch, uri).with_client(self.clone())
     }
 }
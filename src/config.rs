@@ -0,0 +1,156 @@
+//! Client configuration.
+
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_types::headers::{HeaderName, HeaderValue};
+use http_types::Url;
+
+use crate::{Client, HttpClient, Result};
+
+/// A TLS configuration threaded into the underlying `HttpClient` when the
+/// default `h1-client` backend is constructed.
+///
+/// This is a shared [`rustls::ClientConfig`]; use it to add custom root
+/// certificates, pin certificates, or relax verification for self-signed
+/// development servers. The `native-client` backend uses the operating
+/// system's TLS stack and exposes no equivalent runtime knob.
+#[cfg(feature = "h1-client")]
+pub type TlsConfig = std::sync::Arc<rustls::ClientConfig>;
+
+/// Configuration for a [`Client`].
+///
+/// A `Config` collects the settings that should apply to every request a
+/// `Client` sends: a base URL that relative request URIs are resolved
+/// against, a set of default headers (e.g. `Authorization` or `User-Agent`)
+/// merged into each outgoing `Request`, and a default request timeout.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// use std::convert::TryInto;
+/// use std::time::Duration;
+///
+/// let client: surf::Client = surf::Config::new()
+///     .set_base_url(surf::http::Url::parse("https://httpbin.org/")?)
+///     .add_header("User-Agent", "surf")?
+///     .set_timeout(Some(Duration::from_secs(5)))
+///     .try_into()?;
+///
+/// let string = client.get("get").recv_string().await?;
+/// # Ok(()) }
+/// ```
+pub struct Config {
+    /// The base URL relative request URIs are joined against.
+    pub(crate) base_url: Option<Url>,
+    /// Headers merged into every outgoing `Request`.
+    pub(crate) headers: HashMap<HeaderName, HeaderValue>,
+    /// The default per-request timeout.
+    pub(crate) timeout: Option<Duration>,
+    /// TLS configuration threaded into the default `h1-client` backend.
+    #[cfg(feature = "h1-client")]
+    pub(crate) tls_config: Option<TlsConfig>,
+    /// An optional pre-built `HttpClient` backend.
+    pub(crate) http_client: Option<Arc<dyn HttpClient>>,
+}
+
+impl Config {
+    /// Create a new, empty `Config`.
+    pub fn new() -> Self {
+        Self {
+            base_url: None,
+            headers: HashMap::new(),
+            timeout: None,
+            #[cfg(feature = "h1-client")]
+            tls_config: None,
+            http_client: None,
+        }
+    }
+
+    /// Set the base URL that relative request URIs are resolved against.
+    ///
+    /// URIs passed to `Client::get`/`post`/etc. are joined onto this value
+    /// with [`Url::join`], so a base of `https://example.com/api/` and a
+    /// request URI of `users` resolves to `https://example.com/api/users`.
+    pub fn set_base_url(mut self, base_url: Url) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Add a default header sent with every request.
+    ///
+    /// Default headers are merged into each outgoing `Request` before the
+    /// middleware stack runs, and never overwrite a header the caller already
+    /// set on the request.
+    pub fn add_header(
+        mut self,
+        name: impl TryInto<HeaderName>,
+        value: impl TryInto<HeaderValue>,
+    ) -> Result<Self> {
+        let name = name
+            .try_into()
+            .map_err(|_| http_types::Error::from_str(400, "invalid header name"))?;
+        let value = value
+            .try_into()
+            .map_err(|_| http_types::Error::from_str(400, "invalid header value"))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Set the default per-request timeout.
+    ///
+    /// `None` disables the timeout. A timeout set on an individual
+    /// `RequestBuilder` takes precedence over this default.
+    pub fn set_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the TLS configuration threaded into the default `h1-client` backend.
+    ///
+    /// Has no effect when a pre-built backend is supplied via
+    /// [`set_http_client`](Self::set_http_client), since that client carries its
+    /// own TLS setup.
+    #[cfg(feature = "h1-client")]
+    pub fn set_tls_config(mut self, tls_config: Option<TlsConfig>) -> Self {
+        self.tls_config = tls_config;
+        self
+    }
+
+    /// Use a custom `http_client::HttpClient` backend.
+    pub fn set_http_client(mut self, http_client: impl HttpClient) -> Self {
+        self.http_client = Some(Arc::new(http_client));
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("base_url", &self.base_url)
+            .field("headers", &self.headers)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl TryFrom<Config> for Client {
+    type Error = http_types::Error;
+
+    /// Build a `Client` from a `Config`, constructing the default backend if
+    /// none was supplied.
+    fn try_from(config: Config) -> Result<Self> {
+        Client::from_config(config)
+    }
+}
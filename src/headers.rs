@@ -1,7 +1,12 @@
 //! HTTP Headers.
 
+use std::convert::TryFrom;
 use std::iter::{IntoIterator, Iterator};
 
+use http::header::{HeaderName, HeaderValue};
+
+use crate::Result;
+
 /// A collection of HTTP Headers.
 #[derive(Debug)]
 pub struct Headers<'a> {
@@ -14,19 +19,104 @@ impl<'a> Headers<'a> {
         Self { headers }
     }
 
-    /// Get a header.
-    pub fn get(&self, key: &'static str) -> Option<&'_ str> {
-        self.headers.get(key).map(|h| h.to_str().unwrap())
+    /// Get the first value for a header.
+    ///
+    /// Returns `Ok(None)` when the header is absent, and an error when the
+    /// stored value is not valid UTF-8.
+    pub fn get<K>(&self, key: K) -> Result<Option<&str>>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+    {
+        let name = header_name(key)?;
+        match self.headers.get(&name) {
+            Some(value) => Ok(Some(value.to_str().map_err(invalid)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get every value for a header, in insertion order.
+    ///
+    /// Useful for headers that may appear more than once, such as `Set-Cookie`.
+    /// Each yielded item is fallible because an individual value may not be
+    /// valid UTF-8.
+    pub fn get_all<K>(&self, key: K) -> Result<GetAll<'_>>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+    {
+        let name = header_name(key)?;
+        Ok(GetAll(self.headers.get_all(&name).iter()))
+    }
+
+    /// Insert a header, replacing any existing values for it.
+    ///
+    /// Returns the previous value, if one was set. Returns an error if that
+    /// previous value is not valid UTF-8; the new value is stored either way.
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Result<Option<String>>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        let name = header_name(key)?;
+        let value = header_value(value)?;
+        self.headers
+            .insert(name, value)
+            .map(|prev| prev.to_str().map(str::to_owned).map_err(invalid))
+            .transpose()
     }
 
-    /// Set a header.
-    pub fn insert(&mut self, key: &'static str, value: impl AsRef<str>) -> Option<String> {
-        let value = value.as_ref().to_owned();
-        let res = self.headers.insert(key, value.parse().unwrap());
-        res.as_ref().map(|h| h.to_str().unwrap().to_owned())
+    /// Append a value to a header, preserving any existing values.
+    ///
+    /// Returns `true` if the header was already present.
+    pub fn append<K, V>(&mut self, key: K, value: V) -> Result<bool>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        let name = header_name(key)?;
+        let value = header_value(value)?;
+        Ok(self.headers.append(name, value))
+    }
+
+    /// Remove a header, returning its first value if it was set.
+    ///
+    /// Returns an error if that value is not valid UTF-8; the header is
+    /// removed either way.
+    pub fn remove<K>(&mut self, key: K) -> Result<Option<String>>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+    {
+        let name = header_name(key)?;
+        self.headers
+            .remove(&name)
+            .map(|prev| prev.to_str().map(str::to_owned).map_err(invalid))
+            .transpose()
+    }
+
+    /// Returns whether a header is present.
+    pub fn contains_key<K>(&self, key: K) -> Result<bool>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+    {
+        let name = header_name(key)?;
+        Ok(self.headers.contains_key(&name))
     }
 
     /// Iterate over all headers.
+    ///
+    /// # Panics
+    ///
+    /// Unlike the fallible [`get`](Self::get)/[`get_all`](Self::get_all)
+    /// accessors, this iterator yields borrowed `&str` values and panics if a
+    /// header value is not valid UTF-8. Use `get`/`get_all` when values may
+    /// contain non-UTF-8 bytes.
     pub fn iter(&self) -> Iter<'_> {
         Iter(self.headers.iter())
     }
@@ -37,16 +127,42 @@ impl<'a> Headers<'a> {
     }
 }
 
+/// Convert a key into a `HeaderName`, surfacing parse failures as a surf error.
+fn header_name<K>(key: K) -> Result<HeaderName>
+where
+    HeaderName: TryFrom<K>,
+    <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+{
+    HeaderName::try_from(key).map_err(|e| invalid(e.into()))
+}
+
+/// Convert a value into a `HeaderValue`, surfacing parse failures as a surf error.
+fn header_value<V>(value: V) -> Result<HeaderValue>
+where
+    HeaderValue: TryFrom<V>,
+    <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+{
+    HeaderValue::try_from(value).map_err(|e| invalid(e.into()))
+}
+
+/// Wrap an invalid-header error as a `400 Bad Request` surf error.
+fn invalid(err: impl std::fmt::Display) -> http_types::Error {
+    http_types::Error::from_str(http_types::StatusCode::BadRequest, err.to_string())
+}
+
 impl<'a> IntoIterator for Headers<'a> {
     type Item = (&'a str, &'a str);
     type IntoIter = Iter<'a>;
 
+    /// See [`Headers::iter`] — this panics on non-UTF-8 header values.
     fn into_iter(self) -> Self::IntoIter {
         Iter(self.headers.iter())
     }
 }
 
 /// An iterator over headers in `Headers`.
+///
+/// Panics on non-UTF-8 header values; see [`Headers::iter`].
 #[derive(Debug)]
 pub struct Iter<'a>(http::header::Iter<'a, http::header::HeaderValue>);
 
@@ -58,4 +174,16 @@ impl<'a> Iterator for Iter<'a> {
             .next()
             .map(|(key, value)| (key.as_str(), value.to_str().unwrap()))
     }
-}
\ No newline at end of file
+}
+
+/// An iterator over every value stored for a single header.
+#[derive(Debug)]
+pub struct GetAll<'a>(http::header::ValueIter<'a, http::header::HeaderValue>);
+
+impl<'a> Iterator for GetAll<'a> {
+    type Item = Result<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|value| value.to_str().map_err(invalid))
+    }
+}
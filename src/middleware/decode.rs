@@ -0,0 +1,115 @@
+//! Transparent response body decompression.
+
+use http_types::headers::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH};
+use http_types::Body;
+
+use crate::middleware::{Middleware, Next};
+use crate::{Client, Request, Response, Result};
+
+use async_trait::async_trait;
+use futures_util::io::BufReader;
+
+/// Middleware that negotiates and transparently decodes compressed responses.
+///
+/// On the way out it advertises the encodings built into this crate via the
+/// `Accept-Encoding` request header (unless the caller already set one). On the
+/// way back it inspects the response's `Content-Encoding` and, when it matches a
+/// supported encoding, replaces the body with a streaming decoder so that
+/// downstream `recv_bytes`/`recv_string`/`recv_json` observe plaintext. The
+/// now-meaningless `Content-Encoding` and `Content-Length` headers are removed.
+///
+/// The supported encoding set is feature-gated (`gzip`, `deflate`, `brotli`),
+/// mirroring the `gzip, deflate, br` negotiation the `awc` client performs.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// let client = surf::client().with(surf::middleware::Decompress::new());
+/// let res = client.send(surf::get("https://httpbin.org/gzip")).await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Decompress {
+    _private: (),
+}
+
+impl Decompress {
+    /// Create a new `Decompress` middleware.
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+/// Build the `Accept-Encoding` value from the encodings enabled at compile
+/// time, so we never advertise an encoding the decoder can't handle.
+fn accepted_encodings() -> Option<String> {
+    let mut encodings: Vec<&str> = Vec::new();
+    #[cfg(feature = "gzip")]
+    encodings.push("gzip");
+    #[cfg(feature = "deflate")]
+    encodings.push("deflate");
+    #[cfg(feature = "brotli")]
+    encodings.push("br");
+
+    if encodings.is_empty() {
+        None
+    } else {
+        Some(encodings.join(", "))
+    }
+}
+
+#[async_trait]
+impl Middleware for Decompress {
+    async fn handle(&self, mut req: Request, client: Client, next: Next<'_>) -> Result<Response> {
+        if req.header(ACCEPT_ENCODING).is_none() {
+            if let Some(accepted) = accepted_encodings() {
+                req.insert_header(ACCEPT_ENCODING, accepted);
+            }
+        }
+
+        let mut res = next.run(req, client).await?;
+
+        let encoding = res
+            .header(CONTENT_ENCODING)
+            .map(|values| values.last().as_str().trim().to_ascii_lowercase());
+
+        let encoding = match encoding {
+            Some(encoding) => encoding,
+            None => return Ok(res),
+        };
+
+        let body = res.take_body();
+        let reader = BufReader::new(body);
+
+        let decoded: Body = match encoding.as_str() {
+            #[cfg(feature = "gzip")]
+            "gzip" => Body::from_reader(
+                BufReader::new(async_compression::futures::bufread::GzipDecoder::new(reader)),
+                None,
+            ),
+            #[cfg(feature = "deflate")]
+            "deflate" => Body::from_reader(
+                BufReader::new(async_compression::futures::bufread::DeflateDecoder::new(reader)),
+                None,
+            ),
+            #[cfg(feature = "brotli")]
+            "br" => Body::from_reader(
+                BufReader::new(async_compression::futures::bufread::BrotliDecoder::new(reader)),
+                None,
+            ),
+            // An unsupported or `identity` encoding: restore the original body
+            // untouched and leave its headers in place.
+            _ => {
+                res.set_body(reader.into_inner());
+                return Ok(res);
+            }
+        };
+
+        res.set_body(decoded);
+        res.remove_header(CONTENT_ENCODING);
+        res.remove_header(CONTENT_LENGTH);
+        Ok(res)
+    }
+}
@@ -0,0 +1,155 @@
+//! Retry requests that fail transiently.
+
+use std::time::Duration;
+
+use http_types::headers::{HeaderValues, RETRY_AFTER};
+use http_types::{Body, Method, StatusCode, Url};
+
+use crate::middleware::{Middleware, Next};
+use crate::{Client, Request, Response, Result};
+
+use async_trait::async_trait;
+
+/// Middleware that re-sends a request when it fails transiently.
+///
+/// A request is retried on connection errors and on the `408 Request Timeout`,
+/// `429 Too Many Requests`, and `5xx` status codes, up to a configurable number
+/// of attempts with exponential backoff. When the server sends a `Retry-After`
+/// header (in delta-seconds) on a `429` or `503` response it is honored in place
+/// of the computed backoff.
+///
+/// Because `Client::send` consumes the request and bodies are one-shot streams,
+/// `Retry` first buffers the body into memory so an identical request can be
+/// reconstructed for each attempt. Requests whose body length is unknown (a
+/// streaming body) cannot be buffered and are passed through without retrying.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[async_std::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// let client = surf::client().with(surf::middleware::Retry::new(3));
+/// let res = client.send(surf::get("https://httpbin.org/get")).await?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Retry {
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl Retry {
+    /// Create a new `Retry` middleware allowing up to `attempts` total tries.
+    pub fn new(attempts: u32) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            backoff: Duration::from_millis(100),
+        }
+    }
+
+    /// Set the base backoff delay doubled before each subsequent attempt.
+    pub fn set_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// A buffered copy of a `Request` that can be replayed for each retry.
+struct FrozenRequest {
+    method: Method,
+    url: Url,
+    headers: Vec<(http_types::headers::HeaderName, HeaderValues)>,
+    body: Vec<u8>,
+}
+
+impl FrozenRequest {
+    /// Buffer `req` into a replayable form. The caller must only freeze a
+    /// request whose body length is known; see [`Request::len`].
+    async fn freeze(mut req: Request) -> Result<Self> {
+        let body = req.take_body().into_bytes().await?;
+
+        let headers = req
+            .iter()
+            .map(|(name, values)| (name.clone(), values.clone()))
+            .collect();
+
+        Ok(Self {
+            method: req.method(),
+            url: req.url().clone(),
+            headers,
+            body,
+        })
+    }
+
+    /// Reconstruct an identical `Request` for another attempt.
+    fn thaw(&self) -> Request {
+        let mut req = Request::new(self.method, self.url.clone());
+        for (name, values) in &self.headers {
+            req.insert_header(name.clone(), values);
+        }
+        req.set_body(Body::from_bytes(self.body.clone()));
+        req
+    }
+}
+
+/// Whether a response's status warrants a retry.
+fn is_transient(status: StatusCode) -> bool {
+    matches!(status, StatusCode::RequestTimeout | StatusCode::TooManyRequests)
+        || status as u16 >= 500
+}
+
+/// Parse a `Retry-After` delta-seconds value into a delay.
+fn retry_after(res: &Response) -> Option<Duration> {
+    res.header(RETRY_AFTER)
+        .and_then(|values| values.last().as_str().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[async_trait]
+impl Middleware for Retry {
+    async fn handle(&self, req: Request, client: Client, next: Next<'_>) -> Result<Response> {
+        // A streaming body whose length is unknown can't be buffered for replay,
+        // so send it once and pass the result straight through.
+        if req.len().is_none() {
+            return next.run(req, client).await;
+        }
+
+        let frozen = FrozenRequest::freeze(req).await?;
+
+        let mut backoff = self.backoff;
+        // Overwritten on the first iteration; `attempts` is always >= 1.
+        let mut last: Result<Response> = Err(http_types::Error::from_str(
+            StatusCode::InternalServerError,
+            "retry made no attempts",
+        ));
+
+        for attempt in 0..self.attempts {
+            let res = next.clone().run(frozen.thaw(), client.clone()).await;
+
+            let delay = match &res {
+                Ok(res) if is_transient(res.status()) => match res.status() {
+                    StatusCode::TooManyRequests | StatusCode::ServiceUnavailable => {
+                        retry_after(res).unwrap_or(backoff)
+                    }
+                    _ => backoff,
+                },
+                Ok(_) => return res,
+                Err(_) => backoff,
+            };
+            last = res;
+
+            if attempt + 1 < self.attempts {
+                async_std::task::sleep(delay).await;
+                backoff *= 2;
+            }
+        }
+
+        last
+    }
+}